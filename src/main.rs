@@ -1,10 +1,24 @@
-use base64::{Engine as _, engine::general_purpose::STANDARD as BASE64};
+use base64::{
+    Engine as _, engine::general_purpose::STANDARD as BASE64,
+    engine::general_purpose::URL_SAFE_NO_PAD as BASE64_URL_NO_PAD,
+};
 use chrono;
 use dotenv::dotenv;
-use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, encode as jwt_encode};
+use jsonwebtoken::errors::ErrorKind;
+use jsonwebtoken::{
+    Algorithm, DecodingKey, EncodingKey, Header, Validation, decode as jwt_decode,
+    encode as jwt_encode,
+};
+use once_cell::sync::OnceCell;
+use rsa::RsaPrivateKey;
+use rsa::pkcs1::{DecodeRsaPublicKey, EncodeRsaPrivateKey, EncodeRsaPublicKey};
+use rsa::pkcs8::DecodePublicKey;
+use rsa::traits::PublicKeyParts;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::env;
 use std::fmt::Display;
+use std::sync::RwLock;
 use tracing::{Level, debug, error};
 use tracing_subscriber::FmtSubscriber;
 
@@ -16,6 +30,10 @@ struct Config {
     public_der_b64: &'static str,
     jwt_private_env: &'static str,
     jwt_public_env: &'static str,
+    /// An operator deploys ES256 or EdDSA keys by setting this to
+    /// `KeyAlgorithm::EcdsaP256`/`KeyAlgorithm::Ed25519` and pointing the
+    /// existing file/b64file/env paths at keys of that type.
+    algorithm: KeyAlgorithm,
 }
 
 impl Config {
@@ -26,10 +44,30 @@ impl Config {
         public_der_b64: "public.der.b64",
         jwt_private_env: "JWT_PRIVATE",
         jwt_public_env: "JWT_PUBLIC",
+        algorithm: KeyAlgorithm::Rsa,
     };
 }
 
-trait LoadDer {
+/// The family of signing key a `Keys` instance was built from, driving which
+/// `jsonwebtoken` parser and JWT `Algorithm` are used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum KeyAlgorithm {
+    Rsa,
+    EcdsaP256,
+    Ed25519,
+}
+
+impl KeyAlgorithm {
+    fn jwt_algorithm(&self) -> Algorithm {
+        match self {
+            KeyAlgorithm::Rsa => Algorithm::RS256,
+            KeyAlgorithm::EcdsaP256 => Algorithm::ES256,
+            KeyAlgorithm::Ed25519 => Algorithm::EdDSA,
+        }
+    }
+}
+
+trait KeyProvider {
     fn load(&self) -> Result<Keys, AuthError>;
 }
 
@@ -37,49 +75,234 @@ struct FileDerLoader;
 struct B64FileDerLoader;
 struct EnvB64DerLoader;
 
-impl LoadDer for FileDerLoader {
+impl KeyProvider for FileDerLoader {
     fn load(&self) -> Result<Keys, AuthError> {
         let private_key =
             std::fs::read(Config::DEFAULT.private_der).map_err(AuthError::FileReadError)?;
         let public_key =
             std::fs::read(Config::DEFAULT.public_der).map_err(AuthError::FileReadError)?;
-        Ok(Keys::from_der(&private_key, &public_key))
+        Keys::from_bytes(&private_key, &public_key, Config::DEFAULT.algorithm)
     }
 }
 
-impl LoadDer for B64FileDerLoader {
+impl KeyProvider for B64FileDerLoader {
     fn load(&self) -> Result<Keys, AuthError> {
-        let private_key = b64file_to_bytes(Config::DEFAULT.private_der_b64)
-            .map_err(AuthError::Base64DecodeError)?;
-        let public_key = b64file_to_bytes(Config::DEFAULT.public_der_b64)
-            .map_err(AuthError::Base64DecodeError)?;
-        Ok(Keys::from_der(&private_key, &public_key))
+        let private_key = b64file_to_bytes(Config::DEFAULT.private_der_b64)?;
+        let public_key = b64file_to_bytes(Config::DEFAULT.public_der_b64)?;
+        Keys::from_bytes(&private_key, &public_key, Config::DEFAULT.algorithm)
     }
 }
 
-impl LoadDer for EnvB64DerLoader {
+impl KeyProvider for EnvB64DerLoader {
     fn load(&self) -> Result<Keys, AuthError> {
         dotenv().ok();
-        let private_key = env_b64_to_bytes(Config::DEFAULT.jwt_private_env)
-            .map_err(|e| AuthError::EnvVarNotFound(e.to_string()))?;
-        let public_key = env_b64_to_bytes(Config::DEFAULT.jwt_public_env)
-            .map_err(|e| AuthError::EnvVarNotFound(e.to_string()))?;
-        Ok(Keys::from_der(&private_key, &public_key))
+        let private_key = env_b64_to_bytes(Config::DEFAULT.jwt_private_env)?;
+        let public_key = env_b64_to_bytes(Config::DEFAULT.jwt_public_env)?;
+        Keys::from_bytes(&private_key, &public_key, Config::DEFAULT.algorithm)
+    }
+}
+
+/// Wraps another loader and synthesizes a fresh keypair the first time the
+/// inner loader reports its files are missing. Not applied by default, so a
+/// production deployment must opt in rather than silently minting keys.
+struct GeneratingLoader<L: KeyProvider> {
+    inner: L,
+}
+
+impl<L: KeyProvider> GeneratingLoader<L> {
+    fn new(inner: L) -> Self {
+        Self { inner }
+    }
+}
+
+impl<L: KeyProvider> KeyProvider for GeneratingLoader<L> {
+    fn load(&self) -> Result<Keys, AuthError> {
+        match self.inner.load() {
+            Err(AuthError::FileReadError(err)) if err.kind() == std::io::ErrorKind::NotFound => {
+                generate_and_store_keys()
+            }
+            other => other,
+        }
+    }
+}
+
+/// Tries a configured, ordered list of providers and returns the first
+/// success, aggregating every failure if all of them fail.
+struct ChainProvider {
+    providers: Vec<Box<dyn KeyProvider>>,
+}
+
+impl ChainProvider {
+    fn new(providers: Vec<Box<dyn KeyProvider>>) -> Self {
+        Self { providers }
+    }
+
+    /// Builds the chain from `JWT_KEY_SOURCE` (e.g. `"env,b64file,file"`),
+    /// falling back to the fixed order `"file,b64file,env"` when unset; the
+    /// order is not read from `Config`.
+    fn from_env() -> Self {
+        let source = env::var("JWT_KEY_SOURCE").unwrap_or_else(|_| "file,b64file,env".to_owned());
+        let providers = source
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .filter_map(|name| match provider_for_name(name) {
+                Some(provider) => Some(provider),
+                None => {
+                    error!("JWT_KEY_SOURCE: unknown key source '{}', ignoring", name);
+                    None
+                }
+            })
+            .collect();
+
+        Self::new(providers)
+    }
+}
+
+fn provider_for_name(name: &str) -> Option<Box<dyn KeyProvider>> {
+    match name {
+        "file" => Some(Box::new(FileDerLoader)),
+        "b64file" => Some(Box::new(B64FileDerLoader)),
+        "env" => Some(Box::new(EnvB64DerLoader)),
+        _ => None,
+    }
+}
+
+impl KeyProvider for ChainProvider {
+    fn load(&self) -> Result<Keys, AuthError> {
+        let mut errors = Vec::new();
+        for provider in &self.providers {
+            match provider.load() {
+                Ok(keys) => return Ok(keys),
+                Err(err) => errors.push(err),
+            }
+        }
+
+        Err(AuthError::ChainExhausted(errors))
     }
 }
 
-fn b64file_to_bytes(path: &str) -> Result<Vec<u8>, base64::DecodeError> {
-    let b64 = std::fs::read_to_string(path).unwrap();
-    BASE64.decode(b64.trim())
+static KEYS: OnceCell<RwLock<Keys>> = OnceCell::new();
+
+/// Loads keys once via the `ChainProvider` and caches them for the process
+/// lifetime. Call at boot so a misconfigured deployment fails loudly here
+/// rather than lazily on the first token request.
+fn initialize_keys() -> Result<(), AuthError> {
+    let keys = ChainProvider::from_env().load()?;
+    KEYS.set(RwLock::new(keys))
+        .map_err(|_| AuthError::KeysAlreadyInitialized)
+}
+
+/// The process-wide cached keys. Panics if `initialize_keys` hasn't run yet.
+fn keys() -> &'static RwLock<Keys> {
+    KEYS.get()
+        .expect("initialize_keys must be called before keys()")
+}
+
+/// Re-resolves keys via the `ChainProvider` and atomically swaps them into
+/// the cache, for rotation without a process restart.
+fn reload_keys() -> Result<(), AuthError> {
+    let fresh = ChainProvider::from_env().load()?;
+    *keys().write().expect("keys lock poisoned") = fresh;
+    Ok(())
+}
+
+fn generate_and_store_keys() -> Result<Keys, AuthError> {
+    let mut rng = rand::thread_rng();
+    let private_key = RsaPrivateKey::new(&mut rng, 2048)
+        .map_err(|err| AuthError::KeyGeneration(err.to_string()))?;
+    let public_key = private_key.to_public_key();
+
+    let private_der = private_key
+        .to_pkcs1_der()
+        .map_err(|err| AuthError::KeyGeneration(err.to_string()))?;
+    let public_der = public_key
+        .to_pkcs1_der()
+        .map_err(|err| AuthError::KeyGeneration(err.to_string()))?;
+
+    std::fs::write(Config::DEFAULT.private_der, private_der.as_bytes())
+        .map_err(AuthError::FileReadError)?;
+    std::fs::write(Config::DEFAULT.public_der, public_der.as_bytes())
+        .map_err(AuthError::FileReadError)?;
+    std::fs::write(
+        Config::DEFAULT.private_der_b64,
+        BASE64.encode(private_der.as_bytes()),
+    )
+    .map_err(AuthError::FileReadError)?;
+    std::fs::write(
+        Config::DEFAULT.public_der_b64,
+        BASE64.encode(public_der.as_bytes()),
+    )
+    .map_err(AuthError::FileReadError)?;
+
+    Ok(Keys::from_der(
+        private_der.as_bytes(),
+        public_der.as_bytes(),
+        KeyAlgorithm::Rsa,
+    ))
+}
+
+/// A fixed P-256 keypair so `KeyAlgorithm::EcdsaP256` has the same kind of
+/// smoke test as the RSA path above, rather than shipping unexercised. The
+/// private key is PKCS8 DER, since that's what `from_ec_der`'s ring backend
+/// requires (a SEC1-encoded key, the other common EC DER format, fails to
+/// parse there); the public key is the raw uncompressed SEC1 point, matching
+/// what `der_decoding_key`/`ec_p256_point` already expect. Committing a fixed
+/// pair rather than generating one at runtime also avoids pulling in an EC
+/// crate for a single test keypair.
+const EC_TEST_PRIVATE_PKCS8_DER_B64: &str =
+    "MIGHAgEAMBMGByqGSM49AgEGCCqGSM49AwEHBG0wawIBAQQg+9QQK0wkieMIsGkgXKid1DL1J3OTX6Wp2zbLVPEU2NmhRANCAAQjyKQYLkpLXdYJ6+ZCFghTeQdpONAtD4//p+O5hwumptEF2lfFFIZudiNrBhCDsnIgbtdd80dBJjS3WFbxTU1L";
+const EC_TEST_PUBLIC_POINT_B64: &str =
+    "BCPIpBguSktd1gnr5kIWCFN5B2k40C0Pj/+n47mHC6am0QXaV8UUhm52I2sGEIOyciBu113zR0EmNLdYVvFNTUs=";
+
+fn ecdsa_p256_test_keypair() -> (Vec<u8>, Vec<u8>) {
+    let private_der = BASE64
+        .decode(EC_TEST_PRIVATE_PKCS8_DER_B64)
+        .expect("EC_TEST_PRIVATE_PKCS8_DER_B64 is valid base64");
+    let public_der = BASE64
+        .decode(EC_TEST_PUBLIC_POINT_B64)
+        .expect("EC_TEST_PUBLIC_POINT_B64 is valid base64");
+
+    (private_der, public_der)
+}
+
+/// A fixed Ed25519 keypair, for the same reason as `ecdsa_p256_test_keypair`
+/// above: `from_ed_der` needs PKCS8, and a committed pair avoids an extra
+/// crate for a single test keypair. The public key is stored as SPKI DER but
+/// returned as the raw 32-byte key, which is what `der_decoding_key` expects.
+const ED25519_TEST_PRIVATE_PKCS8_DER_B64: &str =
+    "MC4CAQAwBQYDK2VwBCIEIH5Kq+UQpEZmGmavmjnL3f6IgvuMGANIWta4IfGkQqEj";
+const ED25519_TEST_PUBLIC_SPKI_DER_B64: &str =
+    "MCowBQYDK2VwAyEAJ7LzESW5B7q1l0/kQ/MY3Ml90oJ55qMMuQBhLYipnl0=";
+
+fn ed25519_test_keypair() -> (Vec<u8>, Vec<u8>) {
+    let private_der = BASE64
+        .decode(ED25519_TEST_PRIVATE_PKCS8_DER_B64)
+        .expect("ED25519_TEST_PRIVATE_PKCS8_DER_B64 is valid base64");
+    let public_spki_der = BASE64
+        .decode(ED25519_TEST_PUBLIC_SPKI_DER_B64)
+        .expect("ED25519_TEST_PUBLIC_SPKI_DER_B64 is valid base64");
+
+    (private_der, ed25519_raw_public_key(&public_spki_der).to_vec())
+}
+
+fn b64file_to_bytes(path: &str) -> Result<Vec<u8>, AuthError> {
+    let b64 = std::fs::read_to_string(path).map_err(AuthError::FileReadError)?;
+    BASE64
+        .decode(b64.trim())
+        .map_err(AuthError::Base64DecodeError)
 }
 
-fn env_b64_to_bytes(env_var: &str) -> Result<Vec<u8>, env::VarError> {
-    let b64 = env::var(env_var)?;
-    Ok(BASE64.decode(b64.trim()).unwrap())
+fn env_b64_to_bytes(env_var: &str) -> Result<Vec<u8>, AuthError> {
+    let b64 = env::var(env_var).map_err(|err| AuthError::EnvVarNotFound(err.to_string()))?;
+    BASE64
+        .decode(b64.trim())
+        .map_err(AuthError::Base64DecodeError)
 }
 
 fn test_keys(keys: &Keys) -> Result<String, AuthError> {
-    let header = Header::new(Algorithm::RS256);
+    let mut header = Header::new(keys.algorithm.jwt_algorithm());
+    header.kid = Some(keys.kid.clone());
     let claims = Claims {
         sub: "test@domain.com".to_owned(),
         iss: "main.rs".to_owned(),
@@ -91,6 +314,12 @@ fn test_keys(keys: &Keys) -> Result<String, AuthError> {
     })
 }
 
+/// Signs a test token and immediately verifies it, as a genuine smoke test.
+fn round_trip(keys: &Keys) -> Result<Claims, AuthError> {
+    let token = test_keys(keys)?;
+    keys.verify(&token, None)
+}
+
 fn main() {
     // Initialize tracing with debug level
     let subscriber = FmtSubscriber::builder()
@@ -108,34 +337,65 @@ fn main() {
 
     tracing::subscriber::set_global_default(subscriber).expect("Failed to set tracing subscriber");
 
-    // Test FileDerLoader
-    debug!("Testing FileDerLoader...");
-    match FileDerLoader.load() {
-        Ok(keys) => match test_keys(&keys) {
-            Ok(token) => debug!("FileDerLoader works! Token: {}", token),
-            Err(err) => debug!("FileDerLoader failed to create token: {:?}", err),
+    // Resolve keys through the ordered, fail-over provider chain
+    debug!("Testing ChainProvider (JWT_KEY_SOURCE)...");
+    match ChainProvider::from_env().load() {
+        Ok(keys) => match round_trip(&keys) {
+            Ok(claims) => debug!("ChainProvider round-trip ok! Claims: {}", claims),
+            Err(err) => debug!("ChainProvider failed sign/verify round-trip: {:?}", err),
         },
-        Err(err) => error!("FileDerLoader failed to load keys: {:?}", err),
+        Err(err) => error!("ChainProvider failed to load keys: {:?}", err),
     }
 
-    // Test B64FileDerLoader
-    debug!("Testing B64FileDerLoader...");
-    match B64FileDerLoader.load() {
-        Ok(keys) => match test_keys(&keys) {
-            Ok(token) => debug!("B64FileDerLoader works! Token: {}", token),
-            Err(err) => debug!("B64FileDerLoader failed to create token: {:?}", err),
+    // Test GeneratingLoader(FileDerLoader), which mints a keypair on first run
+    debug!("Testing GeneratingLoader(FileDerLoader)...");
+    match GeneratingLoader::new(FileDerLoader).load() {
+        Ok(keys) => match round_trip(&keys) {
+            Ok(claims) => debug!("GeneratingLoader(FileDerLoader) round-trip ok! Claims: {}", claims),
+            Err(err) => debug!(
+                "GeneratingLoader(FileDerLoader) failed sign/verify round-trip: {:?}",
+                err
+            ),
         },
-        Err(err) => error!("B64FileDerLoader failed to load keys: {:?}", err),
+        Err(err) => error!("GeneratingLoader(FileDerLoader) failed to load keys: {:?}", err),
     }
 
-    // Test EnvB64DerLoader
-    debug!("Testing EnvB64DerLoader...");
-    match EnvB64DerLoader.load() {
-        Ok(keys) => match test_keys(&keys) {
-            Ok(token) => debug!("EnvB64DerLoader works! Token: {}", token),
-            Err(err) => debug!("EnvB64DerLoader failed to create token: {:?}", err),
-        },
-        Err(err) => error!("EnvB64DerLoader failed to load keys: {:?}", err),
+    // Emit the JWKS document relying parties would fetch to verify issued tokens
+    debug!("Testing Keys::to_jwks...");
+    match GeneratingLoader::new(FileDerLoader).load() {
+        Ok(keys) => debug!("JWKS: {}", keys.to_jwks(&keys.kid)),
+        Err(err) => error!("Could not load keys to build JWKS: {:?}", err),
+    }
+
+    // Test KeyAlgorithm::EcdsaP256 end-to-end, since Config::DEFAULT stays on Rsa
+    debug!("Testing KeyAlgorithm::EcdsaP256...");
+    let (ec_private_der, ec_public_der) = ecdsa_p256_test_keypair();
+    let ec_keys = Keys::from_der(&ec_private_der, &ec_public_der, KeyAlgorithm::EcdsaP256);
+    let ec_claims = round_trip(&ec_keys).expect("EcdsaP256 sign/verify round-trip must succeed");
+    debug!("EcdsaP256 round-trip ok! Claims: {}", ec_claims);
+    debug!("EcdsaP256 JWKS: {}", ec_keys.to_jwks(&ec_keys.kid));
+
+    // Test KeyAlgorithm::Ed25519 end-to-end, for the same reason as EcdsaP256 above
+    debug!("Testing KeyAlgorithm::Ed25519...");
+    let (ed_private_der, ed_public_der) = ed25519_test_keypair();
+    let ed_keys = Keys::from_der(&ed_private_der, &ed_public_der, KeyAlgorithm::Ed25519);
+    let ed_claims = round_trip(&ed_keys).expect("Ed25519 sign/verify round-trip must succeed");
+    debug!("Ed25519 round-trip ok! Claims: {}", ed_claims);
+    debug!("Ed25519 JWKS: {}", ed_keys.to_jwks(&ed_keys.kid));
+
+    // Load keys once into the process-wide cache, then rotate without a restart
+    debug!("Testing cached keys via initialize_keys()/keys()/reload_keys()...");
+    if let Err(err) = initialize_keys() {
+        error!("Failed to initialize keys at boot: {:?}", err);
+        return;
+    }
+    match round_trip(&keys().read().expect("keys lock poisoned")) {
+        Ok(claims) => debug!("Cached keys round-trip ok! Claims: {}", claims),
+        Err(err) => debug!("Cached keys failed sign/verify round-trip: {:?}", err),
+    }
+    match reload_keys() {
+        Ok(()) => debug!("Keys reloaded from the provider chain"),
+        Err(err) => error!("Failed to reload keys: {:?}", err),
     }
 }
 
@@ -148,15 +408,217 @@ impl Display for Claims {
 struct Keys {
     encoding: EncodingKey,
     decoding: DecodingKey,
+    algorithm: KeyAlgorithm,
+    public_der: Vec<u8>,
+    kid: String,
 }
 
 impl Keys {
-    fn from_der(private_key: &[u8], public_key: &[u8]) -> Self {
-        let encoding = EncodingKey::from_rsa_der(private_key);
-        let decoding = DecodingKey::from_rsa_der(public_key);
+    fn from_der(private_key: &[u8], public_key: &[u8], algorithm: KeyAlgorithm) -> Self {
+        Self {
+            encoding: der_encoding_key(private_key, algorithm),
+            decoding: der_decoding_key(public_key, algorithm),
+            algorithm,
+            kid: fingerprint(public_key),
+            public_der: public_key.to_vec(),
+        }
+    }
+
+    fn from_pem(
+        private_key: &[u8],
+        public_key: &[u8],
+        algorithm: KeyAlgorithm,
+    ) -> Result<Self, AuthError> {
+        let encoding = pem_encoding_key(private_key, algorithm)?;
+        let decoding = pem_decoding_key(public_key, algorithm)?;
+        let public_der = der_from_pem(public_key, algorithm);
+
+        Ok(Self {
+            encoding,
+            decoding,
+            algorithm,
+            kid: fingerprint(&public_der),
+            public_der,
+        })
+    }
+
+    /// Sniffs each key's own leading bytes for a PEM header rather than assuming
+    /// the private and public key share an encoding — they're loaded from
+    /// independent files/env vars, so one can be PEM while the other is DER.
+    fn from_bytes(
+        private_key: &[u8],
+        public_key: &[u8],
+        algorithm: KeyAlgorithm,
+    ) -> Result<Self, AuthError> {
+        if is_pem(private_key) && is_pem(public_key) {
+            return Self::from_pem(private_key, public_key, algorithm);
+        }
+
+        let encoding = if is_pem(private_key) {
+            pem_encoding_key(private_key, algorithm)?
+        } else {
+            der_encoding_key(private_key, algorithm)
+        };
+
+        let (decoding, public_der) = if is_pem(public_key) {
+            (
+                pem_decoding_key(public_key, algorithm)?,
+                der_from_pem(public_key, algorithm),
+            )
+        } else {
+            (der_decoding_key(public_key, algorithm), public_key.to_vec())
+        };
+
+        Ok(Self {
+            encoding,
+            decoding,
+            algorithm,
+            kid: fingerprint(&public_der),
+            public_der,
+        })
+    }
+
+    /// Decodes and validates a token, checking `exp` and, if given, a required `iss`.
+    fn verify(&self, token: &str, required_iss: Option<&str>) -> Result<Claims, AuthError> {
+        let mut validation = Validation::new(self.algorithm.jwt_algorithm());
+        if let Some(iss) = required_iss {
+            validation.set_issuer(&[iss]);
+        }
+
+        jwt_decode::<Claims>(token, &self.decoding, &validation)
+            .map(|data| data.claims)
+            .map_err(|err| match err.kind() {
+                ErrorKind::ExpiredSignature => AuthError::TokenExpired,
+                ErrorKind::InvalidSignature => AuthError::InvalidSignature,
+                ErrorKind::InvalidIssuer => AuthError::InvalidIssuer,
+                _ => AuthError::TokenValidation(err),
+            })
+    }
+
+    /// Emits the public key as a standard JWK Set so relying parties can verify
+    /// tokens this crate issues without needing the raw key material.
+    fn to_jwks(&self, kid: &str) -> serde_json::Value {
+        match self.algorithm {
+            KeyAlgorithm::Rsa => {
+                let public_key = rsa::RsaPublicKey::from_pkcs1_der(&self.public_der)
+                    .expect("Keys was constructed from a valid RSA public key");
+                serde_json::json!({
+                    "keys": [{
+                        "kty": "RSA",
+                        "use": "sig",
+                        "alg": "RS256",
+                        "kid": kid,
+                        "n": BASE64_URL_NO_PAD.encode(public_key.n().to_bytes_be()),
+                        "e": BASE64_URL_NO_PAD.encode(public_key.e().to_bytes_be()),
+                    }]
+                })
+            }
+            KeyAlgorithm::EcdsaP256 => {
+                let (x, y) = ec_p256_point(&self.public_der);
+                serde_json::json!({
+                    "keys": [{
+                        "kty": "EC",
+                        "use": "sig",
+                        "alg": "ES256",
+                        "kid": kid,
+                        "crv": "P-256",
+                        "x": BASE64_URL_NO_PAD.encode(x),
+                        "y": BASE64_URL_NO_PAD.encode(y),
+                    }]
+                })
+            }
+            KeyAlgorithm::Ed25519 => {
+                serde_json::json!({
+                    "keys": [{
+                        "kty": "OKP",
+                        "use": "sig",
+                        "alg": "EdDSA",
+                        "kid": kid,
+                        "crv": "Ed25519",
+                        "x": BASE64_URL_NO_PAD.encode(ed25519_raw_public_key(&self.public_der)),
+                    }]
+                })
+            }
+        }
+    }
+}
+
+fn fingerprint(public_key: &[u8]) -> String {
+    Sha256::digest(public_key)
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect()
+}
+
+/// Splits an uncompressed SEC1 P-256 point (`0x04 || x || y`) into its coordinates.
+fn ec_p256_point(sec1_bytes: &[u8]) -> (&[u8], &[u8]) {
+    let point = &sec1_bytes[sec1_bytes.len() - 64..];
+    point.split_at(32)
+}
+
+/// Ed25519 SPKI DER is a fixed 12-byte prefix followed by the raw 32-byte public key.
+fn ed25519_raw_public_key(spki_der: &[u8]) -> &[u8] {
+    &spki_der[spki_der.len() - 32..]
+}
+
+fn is_pem(bytes: &[u8]) -> bool {
+    bytes.starts_with(b"-----BEGIN")
+}
+
+fn der_encoding_key(bytes: &[u8], algorithm: KeyAlgorithm) -> EncodingKey {
+    match algorithm {
+        KeyAlgorithm::Rsa => EncodingKey::from_rsa_der(bytes),
+        KeyAlgorithm::EcdsaP256 => EncodingKey::from_ec_der(bytes),
+        KeyAlgorithm::Ed25519 => EncodingKey::from_ed_der(bytes),
+    }
+}
+
+fn der_decoding_key(bytes: &[u8], algorithm: KeyAlgorithm) -> DecodingKey {
+    match algorithm {
+        KeyAlgorithm::Rsa => DecodingKey::from_rsa_der(bytes),
+        KeyAlgorithm::EcdsaP256 => DecodingKey::from_ec_der(bytes),
+        KeyAlgorithm::Ed25519 => DecodingKey::from_ed_der(bytes),
+    }
+}
+
+fn pem_encoding_key(bytes: &[u8], algorithm: KeyAlgorithm) -> Result<EncodingKey, AuthError> {
+    match algorithm {
+        KeyAlgorithm::Rsa => EncodingKey::from_rsa_pem(bytes),
+        KeyAlgorithm::EcdsaP256 => EncodingKey::from_ec_pem(bytes),
+        KeyAlgorithm::Ed25519 => EncodingKey::from_ed_pem(bytes),
+    }
+    .map_err(AuthError::KeyParse)
+}
+
+fn pem_decoding_key(bytes: &[u8], algorithm: KeyAlgorithm) -> Result<DecodingKey, AuthError> {
+    match algorithm {
+        KeyAlgorithm::Rsa => DecodingKey::from_rsa_pem(bytes),
+        KeyAlgorithm::EcdsaP256 => DecodingKey::from_ec_pem(bytes),
+        KeyAlgorithm::Ed25519 => DecodingKey::from_ed_pem(bytes),
+    }
+    .map_err(AuthError::KeyParse)
+}
+
+/// JWKS export needs DER; PEM-sourced public keys are unwrapped back to it.
+/// RSA keys are additionally normalized to bare PKCS1: the common
+/// `openssl rsa -pubout` PEM (`-----BEGIN PUBLIC KEY-----`) wraps the key as
+/// SPKI, but `to_jwks`'s RSA arm parses PKCS1 directly and would otherwise
+/// panic on the most common RSA public-key PEM format.
+fn der_from_pem(pem_bytes: &[u8], algorithm: KeyAlgorithm) -> Vec<u8> {
+    let contents = pem::parse(pem_bytes)
+        .map(|p| p.contents().to_vec())
+        .unwrap_or_else(|_| pem_bytes.to_vec());
 
-        Self { encoding, decoding }
+    if algorithm == KeyAlgorithm::Rsa {
+        if let Some(pkcs1_der) = rsa::RsaPublicKey::from_public_key_der(&contents)
+            .ok()
+            .and_then(|public_key| public_key.to_pkcs1_der().ok())
+        {
+            return pkcs1_der.as_bytes().to_vec();
+        }
     }
+
+    contents
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -172,4 +634,12 @@ enum AuthError {
     FileReadError(std::io::Error),
     Base64DecodeError(base64::DecodeError),
     EnvVarNotFound(String),
+    KeyParse(jsonwebtoken::errors::Error),
+    KeyGeneration(String),
+    TokenExpired,
+    InvalidSignature,
+    InvalidIssuer,
+    TokenValidation(jsonwebtoken::errors::Error),
+    ChainExhausted(Vec<AuthError>),
+    KeysAlreadyInitialized,
 }